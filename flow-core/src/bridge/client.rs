@@ -1,29 +1,116 @@
 use log::{debug, info, trace};
 
+use std::collections::HashMap;
 use std::io::{Error, ErrorKind, Result};
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
 use url::Url;
 
-use tokio::io::AsyncRead;
+use byteorder::{ByteOrder, LittleEndian};
+
+use futures::future::join_all;
+use futures::sync::{mpsc, oneshot};
+use futures::{Future, Sink, Stream};
+
+use tokio::codec::Decoder;
 use tokio::net::TcpStream;
-use tokio::prelude::*;
 use tokio::runtime::current_thread::Runtime;
 
 #[cfg(any(unix))]
 use tokio::net::UnixStream;
 
-use capnp::capability::Promise;
-use capnp_rpc::{pry, rpc_twoparty_capnp, twoparty, RpcSystem};
-
-use crate::bridge_capnp::bridge;
+#[cfg(any(unix))]
+use std::os::unix::io::{AsRawFd, RawFd};
 
 use crate::address::{Address, Length};
 use crate::arch::Architecture;
+use crate::bridge::codec::{BridgeCodec, Frame, Op, TAG_RESPONSE};
+use crate::mem::shared_memory::SharedMemoryConnector;
 use crate::mem::{PhysicalRead, PhysicalWrite, VirtualRead, VirtualWrite};
 
+/// Pending response slots, keyed by the `request_id` the request frame was
+/// sent with, so replies can resolve the right caller even when several
+/// requests are in flight and the server answers them out of order.
+type PendingMap = Arc<Mutex<HashMap<u32, oneshot::Sender<Frame>>>>;
+
 pub struct BridgeClient {
-    bridge: bridge::Client,
     runtime: Runtime,
+    next_request_id: Arc<AtomicU32>,
+    pending: PendingMap,
+    frame_tx: mpsc::UnboundedSender<Frame>,
+    // zero-copy fast path handed over via SCM_RIGHTS on `connect_unix`;
+    // `None` when the server only speaks the framed RPC protocol
+    shared_mem: Option<SharedMemoryConnector>,
+}
+
+/// Drops every pending response slot in `pending`, so any caller currently
+/// blocked on one of its `oneshot::Receiver`s in [`BridgeClient::await_all`]
+/// observes a `Canceled` error instead of hanging forever once the
+/// connection it was waiting on is gone.
+fn fail_pending(pending: &PendingMap) {
+    pending.lock().unwrap().clear();
+}
+
+/// Spawns the reader/writer halves of the framed bridge protocol onto
+/// `runtime`, returning a sender used to enqueue outgoing frames and the
+/// table incoming replies are resolved against.
+///
+/// Frames queued on the returned sender are written to the wire as soon as
+/// they arrive, so a caller can submit many requests before awaiting any of
+/// their replies, turning what used to be N blocking round-trips into one
+/// pipelined burst. If either half of the connection ends, for any reason,
+/// every still-pending request is failed via [`fail_pending`] so callers
+/// observe an error instead of blocking forever.
+fn spawn_transport<T>(runtime: &mut Runtime, stream: T) -> (mpsc::UnboundedSender<Frame>, PendingMap)
+where
+    T: ::tokio::io::AsyncRead + ::tokio::io::AsyncWrite + 'static,
+{
+    let (frame_tx, frame_rx) = mpsc::unbounded::<Frame>();
+    let pending: PendingMap = Arc::new(Mutex::new(HashMap::new()));
+
+    let framed = BridgeCodec.framed(stream);
+    let (sink, source) = framed.split();
+
+    let pending_writer = pending.clone();
+    runtime.spawn(
+        sink.send_all(frame_rx.map_err(|_e| Error::new(ErrorKind::Other, "frame channel closed")))
+            .then(move |res| {
+                if let Err(e) = &res {
+                    debug!("bridge write loop terminated: {}", e);
+                }
+                // the write loop only ends when the connection is gone, so
+                // no reply is ever coming for whatever is still pending
+                fail_pending(&pending_writer);
+                Ok::<(), ()>(())
+            }),
+    );
+
+    let pending_reader = pending.clone();
+    let pending_reader_done = pending.clone();
+    runtime.spawn(
+        source
+            .for_each(move |frame| {
+                if frame.tag == TAG_RESPONSE {
+                    if let Some(tx) = pending_reader.lock().unwrap().remove(&frame.request_id) {
+                        let _ = tx.send(frame);
+                    }
+                }
+                Ok(())
+            })
+            .then(move |res| {
+                if let Err(e) = &res {
+                    debug!("bridge read loop terminated: {}", e);
+                }
+                // same reasoning as the write loop: once the reader is
+                // gone nothing still pending will ever be resolved
+                fail_pending(&pending_reader_done);
+                Ok::<(), ()>(())
+            }),
+    );
+
+    (frame_tx, pending)
 }
 
 #[cfg(any(unix))]
@@ -32,16 +119,117 @@ fn connect_unix(path: &str, opts: Vec<&str>) -> Result<BridgeClient> {
 
     let mut runtime = Runtime::new().unwrap();
     let stream = runtime.block_on(UnixStream::connect(path))?;
-    let (reader, writer) = stream.split();
 
     info!("unix connection established -> {}", path);
 
+    // a standard bridge server never sends an SCM_RIGHTS message, so
+    // probing for one unconditionally would add a flat
+    // `SHARED_MEMORY_HANDSHAKE_TIMEOUT_MS` to every connect and risks
+    // consuming a byte of the framed protocol if that server ever writes
+    // before the deadline; only probe when the caller opted in via the
+    // `shared_mem` url option, which is the contract a server handing out a
+    // zero-copy region is expected to uphold.
+    let shared_mem = if opts.iter().any(|&o| o == "shared_mem") {
+        // the server may hand over a zero-copy shared-memory region via an
+        // out-of-band SCM_RIGHTS control message before the framed protocol
+        // starts; when it does, prefer mmap-backed reads over RPC for any
+        // range that falls inside it
+        match recv_shared_memory_fd(&stream) {
+            Ok(Some(fd)) => {
+                info!("received shared memory fd {} via SCM_RIGHTS", fd);
+                match unsafe { SharedMemoryConnector::from_raw_fd(fd) } {
+                    Ok(conn) => Some(conn),
+                    Err(e) => {
+                        debug!("unable to mmap shared memory fd: {}", e);
+                        None
+                    }
+                }
+            }
+            Ok(None) => None,
+            Err(e) => {
+                debug!("no shared memory fd received: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let (frame_tx, pending) = spawn_transport(&mut runtime, stream);
+
     Ok(BridgeClient {
-        bridge: connect_rpc(&mut runtime, reader, writer)?,
-        runtime: runtime,
+        runtime,
+        next_request_id: Arc::new(AtomicU32::new(0)),
+        pending,
+        frame_tx,
+        shared_mem,
     })
 }
 
+/// How long [`recv_shared_memory_fd`] blocks waiting for the server's
+/// `SCM_RIGHTS` handshake before concluding it isn't coming.
+#[cfg(any(unix))]
+const SHARED_MEMORY_HANDSHAKE_TIMEOUT_MS: i32 = 250;
+
+/// Waits (up to [`SHARED_MEMORY_HANDSHAKE_TIMEOUT_MS`]) on the not-yet-framed
+/// unix socket for an ancillary `SCM_RIGHTS` message, returning the first
+/// file descriptor it carries, if any.
+///
+/// A single non-blocking attempt here would race the server's send: if the
+/// control message (and its one-byte guard payload) hasn't landed in the
+/// kernel socket buffer yet, `MSG_DONTWAIT` returns `EAGAIN` without
+/// consuming anything, so the byte is consumed by the framed codec instead
+/// once the connection is handed off, permanently desyncing the
+/// length-delimited protocol. `tokio::net::UnixStream` is always
+/// non-blocking (the reactor requires it), so `SO_RCVTIMEO` has no effect on
+/// it; `poll()` the raw fd for the bounded timeout instead and only attempt
+/// `recvmsg` once it actually reports readable, so the byte and its FD are
+/// always consumed together: either both arrive before the deadline, or
+/// neither is read at all.
+///
+/// Must run before the socket is handed to [`spawn_transport`], since after
+/// that point its bytes are consumed by the framed codec instead.
+#[cfg(any(unix))]
+fn recv_shared_memory_fd(stream: &UnixStream) -> Result<Option<RawFd>> {
+    use nix::errno::Errno;
+    use nix::poll::{poll, PollFd, PollFlags};
+    use nix::sys::socket::{recvmsg, ControlMessageOwned, MsgFlags};
+    use nix::sys::uio::IoVec;
+    use nix::Error as NixError;
+
+    let raw_fd = stream.as_raw_fd();
+
+    let mut poll_fds = [PollFd::new(raw_fd, PollFlags::POLLIN)];
+    match poll(&mut poll_fds, SHARED_MEMORY_HANDSHAKE_TIMEOUT_MS) {
+        Ok(0) => return Ok(None),
+        Ok(_) => {}
+        Err(e) => return Err(Error::new(ErrorKind::Other, e)),
+    }
+
+    let mut buf = [0u8; 1];
+    let iov = [IoVec::from_mut_slice(&mut buf)];
+    let mut cmsg_buf = nix::cmsg_space!(RawFd);
+    let result = recvmsg(raw_fd, &iov, Some(&mut cmsg_buf), MsgFlags::empty());
+
+    let msg = match result {
+        Ok(msg) => msg,
+        Err(NixError::Sys(Errno::EAGAIN)) | Err(NixError::Sys(Errno::EWOULDBLOCK)) => {
+            return Ok(None)
+        }
+        Err(e) => return Err(Error::new(ErrorKind::Other, e)),
+    };
+
+    for cmsg in msg.cmsgs() {
+        if let ControlMessageOwned::ScmRights(fds) = cmsg {
+            if let Some(fd) = fds.into_iter().nth(0) {
+                return Ok(Some(fd));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
 #[cfg(not(any(unix)))]
 fn connect_unix(path: &str, opts: Vec<&str>) -> Result<BridgeClient> {
     Err(Error::new(
@@ -67,34 +255,17 @@ fn connect_tcp(path: &str, opts: Vec<&str>) -> Result<BridgeClient> {
         stream.set_nodelay(true).unwrap();
     }
 
-    let (reader, writer) = stream.split();
+    let (frame_tx, pending) = spawn_transport(&mut runtime, stream);
 
     Ok(BridgeClient {
-        bridge: connect_rpc(&mut runtime, reader, writer)?,
-        runtime: runtime,
+        runtime,
+        next_request_id: Arc::new(AtomicU32::new(0)),
+        pending,
+        frame_tx,
+        shared_mem: None,
     })
 }
 
-fn connect_rpc<T, U>(runtime: &mut Runtime, reader: T, writer: U) -> Result<bridge::Client>
-where
-    T: ::std::io::Read + 'static,
-    U: ::std::io::Write + 'static,
-{
-    let network = Box::new(twoparty::VatNetwork::new(
-        reader,
-        std::io::BufWriter::new(writer),
-        rpc_twoparty_capnp::Side::Client,
-        Default::default(),
-    ));
-
-    let mut rpc_system = RpcSystem::new(network, None);
-    let bridge: bridge::Client = rpc_system.bootstrap(rpc_twoparty_capnp::Side::Server);
-
-    runtime.spawn(rpc_system.map_err(|_e| ()));
-
-    Ok(bridge)
-}
-
 impl BridgeClient {
     pub fn connect(urlstr: &str) -> Result<BridgeClient> {
         let url = Url::parse(urlstr).map_err(|e| Error::new(ErrorKind::Other, e))?;
@@ -113,55 +284,213 @@ impl BridgeClient {
         }
     }
 
+    fn next_id(&self) -> u32 {
+        self.next_request_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Writes `op`/`payload` onto the wire as a new request frame and
+    /// registers a slot for its reply, returning a future that resolves once
+    /// the response frame with the matching `request_id` arrives.
+    ///
+    /// The frame is sent immediately, before the returned future is ever
+    /// polled, so callers can submit a whole batch of requests up front and
+    /// only then await them together.
+    fn submit(&self, op: Op, payload: Vec<u8>) -> oneshot::Receiver<Frame> {
+        let request_id = self.next_id();
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(request_id, tx);
+        let _ = self
+            .frame_tx
+            .unbounded_send(Frame::request(request_id, op, payload));
+        rx
+    }
+
+    fn await_all(&mut self, receivers: Vec<oneshot::Receiver<Frame>>) -> Result<Vec<Frame>> {
+        let joined = join_all(receivers.into_iter().map(|rx| {
+            rx.map_err(|_e| Error::new(ErrorKind::Other, "bridge connection closed"))
+        }));
+        self.runtime.block_on(joined)
+    }
+
     pub fn read_registers(&mut self) -> Result<Vec<u8>> {
-        let request = self.bridge.read_registers_request();
-        self.runtime
-            .block_on(request.send().promise.and_then(|_r| Promise::ok(())))
-            .map_err(|_e| Error::new(ErrorKind::Other, "unable to read registers"))
-            .and_then(|_v| Ok(Vec::new()))
+        let rx = self.submit(Op::RegistersRead, Vec::new());
+        self.await_all(vec![rx])
+            .map(|mut frames| frames.remove(0).payload)
+    }
+
+    /// Reads many physical memory ranges in a single pipelined burst: every
+    /// sub-request is written to the wire before any reply is awaited, so a
+    /// `virt_batcher` flush that expands into several `phys_read`s becomes
+    /// one pipelined round-trip instead of `N` blocking ones.
+    pub fn phys_read_list(&mut self, reads: &[(Address, Length)]) -> Result<Vec<Vec<u8>>> {
+        let receivers = reads
+            .iter()
+            .map(|(addr, len)| {
+                let mut payload = vec![0u8; 16];
+                LittleEndian::write_u64(&mut payload[0..8], addr.as_u64());
+                LittleEndian::write_u64(&mut payload[8..16], len.as_u64());
+                self.submit(Op::PhysRead, payload)
+            })
+            .collect();
+
+        self.await_all(receivers)
+            .map(|frames| frames.into_iter().map(|f| f.payload).collect())
+    }
+
+    /// Reads many discontiguous physical memory ranges directly into the
+    /// caller-provided buffers.
+    ///
+    /// Descriptors covered by the zero-copy shared mapping (if any) are
+    /// served straight out of it; every remaining descriptor is packed into
+    /// a single `PhysReadVectored` frame so the whole batch becomes one
+    /// framed request instead of one round-trip per range.
+    pub fn phys_read_vectored(&mut self, data: &mut [(Address, &mut [u8])]) -> Result<()> {
+        let mut rpc_indices = Vec::new();
+        let mut payload = vec![0u8; 4];
+
+        for (i, (addr, buf)) in data.iter().enumerate() {
+            let len = Length::from(buf.len() as u64);
+            if let Some(shared) = self.shared_mem.as_ref() {
+                if shared.contains(*addr, len) {
+                    continue;
+                }
+            }
+
+            rpc_indices.push(i);
+            let mut descriptor = [0u8; 16];
+            LittleEndian::write_u64(&mut descriptor[0..8], addr.as_u64());
+            LittleEndian::write_u64(&mut descriptor[8..16], buf.len() as u64);
+            payload.extend_from_slice(&descriptor);
+        }
+        LittleEndian::write_u32(&mut payload[0..4], rpc_indices.len() as u32);
+
+        let rpc_payload = if rpc_indices.is_empty() {
+            Vec::new()
+        } else {
+            let rx = self.submit(Op::PhysReadVectored, payload);
+            self.await_all(vec![rx])?.remove(0).payload
+        };
+
+        let mut rpc_indices = rpc_indices.into_iter().peekable();
+        let mut offset = 0usize;
+
+        for (i, (addr, buf)) in data.iter_mut().enumerate() {
+            if rpc_indices.peek() == Some(&i) {
+                rpc_indices.next();
+                if offset + buf.len() > rpc_payload.len() {
+                    return Err(Error::new(
+                        ErrorKind::Other,
+                        "malformed phys_read_vectored reply",
+                    ));
+                }
+                buf.copy_from_slice(&rpc_payload[offset..offset + buf.len()]);
+                offset += buf.len();
+            } else if let Some(shared) = self.shared_mem.as_mut() {
+                let read = shared.phys_read(*addr, Length::from(buf.len() as u64))?;
+                let n = buf.len().min(read.len());
+                buf[..n].copy_from_slice(&read[..n]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes many discontiguous physical memory ranges from the
+    /// caller-provided buffers, packing every range into a single
+    /// `PhysWriteVectored` frame the same way [`phys_read_vectored`]
+    /// packs its reads.
+    pub fn phys_write_vectored(&mut self, data: &[(Address, &[u8])]) -> Result<()> {
+        let mut headers = vec![0u8; 4];
+        let mut bodies = Vec::new();
+
+        for (addr, buf) in data.iter() {
+            let mut descriptor = [0u8; 16];
+            LittleEndian::write_u64(&mut descriptor[0..8], addr.as_u64());
+            LittleEndian::write_u64(&mut descriptor[8..16], buf.len() as u64);
+            headers.extend_from_slice(&descriptor);
+            bodies.extend_from_slice(buf);
+        }
+        LittleEndian::write_u32(&mut headers[0..4], data.len() as u32);
+        headers.extend_from_slice(&bodies);
+
+        let rx = self.submit(Op::PhysWriteVectored, headers);
+        let payload = self.await_all(vec![rx])?.remove(0).payload;
+
+        if payload.len() < 8 * data.len() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                "malformed phys_write_vectored reply",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Reads many virtual memory ranges sharing the same `arch`/`dtb` in a
+    /// single pipelined burst, mirroring [`phys_read_list`](Self::phys_read_list).
+    pub fn virt_read_list(
+        &mut self,
+        arch: Architecture,
+        dtb: Address,
+        reads: &[(Address, Length)],
+    ) -> Result<Vec<Vec<u8>>> {
+        let receivers = reads
+            .iter()
+            .map(|(addr, len)| {
+                let mut payload = vec![0u8; 25];
+                payload[0] = arch.instruction_set.as_u8();
+                LittleEndian::write_u64(&mut payload[1..9], dtb.as_u64());
+                LittleEndian::write_u64(&mut payload[9..17], addr.as_u64());
+                LittleEndian::write_u64(&mut payload[17..25], len.as_u64());
+                self.submit(Op::VirtRead, payload)
+            })
+            .collect();
+
+        self.await_all(receivers)
+            .map(|frames| frames.into_iter().map(|f| f.payload).collect())
     }
 }
 
 impl PhysicalRead for BridgeClient {
-    // physRead @0 (address :UInt64, length :UInt64) -> (data :Data);
     fn phys_read(&mut self, addr: Address, len: Length) -> Result<Vec<u8>> {
         trace!("phys_read({:?}, {:?})", addr, len);
 
-        let mut request = self.bridge.phys_read_request();
-        request.get().set_address(addr.as_u64());
-        request.get().set_length(len.as_u64());
-        self.runtime
-            .block_on(
-                request.send().promise.and_then(|response| {
-                    Promise::ok(Vec::from(pry!(pry!(response.get()).get_data())))
-                }),
-            )
-            .map_err(|_e| Error::new(ErrorKind::Other, "unable to read memory"))
-            .and_then(|v| Ok(v))
+        // turn repeated reads against local targets into pointer arithmetic
+        // instead of socket traffic whenever the range is covered by the
+        // zero-copy mapping
+        if let Some(shared) = self.shared_mem.as_mut() {
+            if shared.contains(addr, len) {
+                return shared.phys_read(addr, len);
+            }
+        }
+
+        self.phys_read_list(&[(addr, len)])?
+            .into_iter()
+            .nth(0)
+            .ok_or_else(|| Error::new(ErrorKind::Other, "unable to read memory"))
     }
 }
 
 impl PhysicalWrite for BridgeClient {
-    // physWrite @1 (address :UInt64, data: Data) -> (length :UInt64);
     fn phys_write(&mut self, addr: Address, data: &Vec<u8>) -> Result<Length> {
         trace!("phys_write({:?})", addr);
 
-        let mut request = self.bridge.phys_write_request();
-        request.get().set_address(addr.as_u64());
-        request.get().set_data(data);
-        self.runtime
-            .block_on(
-                request.send().promise.and_then(|response| {
-                    Promise::ok(Length::from(pry!(response.get()).get_length()))
-                }),
-            )
-            .map_err(|_e| Error::new(ErrorKind::Other, "unable to write memory"))
-            .and_then(|v| Ok(v))
+        let mut payload = vec![0u8; 8 + data.len()];
+        LittleEndian::write_u64(&mut payload[0..8], addr.as_u64());
+        payload[8..].copy_from_slice(data);
+
+        let rx = self.submit(Op::PhysWrite, payload);
+        self.await_all(vec![rx]).and_then(|mut frames| {
+            let payload = frames.remove(0).payload;
+            if payload.len() < 8 {
+                return Err(Error::new(ErrorKind::Other, "malformed phys_write reply"));
+            }
+            Ok(Length::from(LittleEndian::read_u64(&payload[0..8])))
+        })
     }
 }
 
 impl BridgeClient {
-    // virtRead @2 (arch: UInt8, dtb :UInt64, address :UInt64, length :UInt64) -> (data: Data);
     fn virt_read_chunk(
         &mut self,
         arch: Architecture,
@@ -169,22 +498,12 @@ impl BridgeClient {
         addr: Address,
         len: Length,
     ) -> Result<Vec<u8>> {
-        let mut request = self.bridge.virt_read_request();
-        request.get().set_arch(arch.instruction_set.as_u8());
-        request.get().set_dtb(dtb.as_u64());
-        request.get().set_address(addr.as_u64());
-        request.get().set_length(len.as_u64());
-        self.runtime
-            .block_on(
-                request.send().promise.and_then(|response| {
-                    Promise::ok(Vec::from(pry!(pry!(response.get()).get_data())))
-                }),
-            )
-            .map_err(|_e| Error::new(ErrorKind::Other, "unable to read memory"))
-            .and_then(|v| Ok(v))
+        self.virt_read_list(arch, dtb, &[(addr, len)])?
+            .into_iter()
+            .nth(0)
+            .ok_or_else(|| Error::new(ErrorKind::Other, "unable to read memory"))
     }
 
-    // virtWrite @3 (arch: UInt8, dtb: UInt64, address :UInt64, data: Data) -> (length :UInt64);
     fn virt_write_chunk(
         &mut self,
         arch: Architecture,
@@ -192,26 +511,28 @@ impl BridgeClient {
         addr: Address,
         data: &Vec<u8>,
     ) -> Result<Length> {
-        let mut request = self.bridge.virt_write_request();
-        request.get().set_arch(arch.instruction_set.as_u8());
-        request.get().set_dtb(dtb.as_u64());
-        request.get().set_address(addr.as_u64());
-        request.get().set_data(data);
-        self.runtime
-            .block_on(
-                request.send().promise.and_then(|response| {
-                    Promise::ok(Length::from(pry!(response.get()).get_length()))
-                }),
-            )
-            .map_err(|_e| Error::new(ErrorKind::Other, "unable to write memory"))
-            .and_then(|v| Ok(v))
+        let mut payload = vec![0u8; 17 + data.len()];
+        payload[0] = arch.instruction_set.as_u8();
+        LittleEndian::write_u64(&mut payload[1..9], dtb.as_u64());
+        LittleEndian::write_u64(&mut payload[9..17], addr.as_u64());
+        payload[17..].copy_from_slice(data);
+
+        let rx = self.submit(Op::VirtWrite, payload);
+        self.await_all(vec![rx]).and_then(|mut frames| {
+            let payload = frames.remove(0).payload;
+            if payload.len() < 8 {
+                return Err(Error::new(ErrorKind::Other, "malformed virt_write reply"));
+            }
+            Ok(Length::from(LittleEndian::read_u64(&payload[0..8])))
+        })
     }
 }
 
-//
-// TODO: split up sections greater than 32mb into multiple packets due to capnp limitations!
-//
 impl VirtualRead for BridgeClient {
+    // what used to be manual >32mb chunk-splitting with one blocking
+    // round-trip per chunk is now just multiple frames pipelined onto the
+    // same burst: `virt_read_list` submits them all up front and we stitch
+    // the replies back together in order.
     fn virt_read(
         &mut self,
         arch: Architecture,
@@ -223,8 +544,8 @@ impl VirtualRead for BridgeClient {
 
         if len > Length::from_mb(32) {
             info!("virt_read(): reading multiple 32mb chunks");
-            let mut result: Vec<u8> = vec![0; len.as_usize()];
 
+            let mut chunks = Vec::new();
             let mut base = addr;
             let end = addr + len;
             while base < end {
@@ -232,15 +553,17 @@ impl VirtualRead for BridgeClient {
                 if base + clamped_len > end {
                     clamped_len = end - base;
                 }
+                chunks.push((base, clamped_len));
+                base += clamped_len;
+            }
 
-                info!("virt_read(): reading chunk at {:x}", base);
-                let mem = self.virt_read_chunk(arch, dtb, base, clamped_len)?;
-                let start = (base - addr).as_usize();
-                mem.iter().enumerate().for_each(|(i, b)| {
-                    result[start + i] = *b;
-                });
+            let parts = self.virt_read_list(arch, dtb, &chunks)?;
 
-                base += clamped_len;
+            let mut result: Vec<u8> = vec![0; len.as_usize()];
+            let mut start = 0usize;
+            for part in parts {
+                result[start..start + part.len()].copy_from_slice(&part);
+                start += part.len();
             }
 
             Ok(result)
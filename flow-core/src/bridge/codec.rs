@@ -0,0 +1,143 @@
+use std::io;
+
+use byteorder::{ByteOrder, LittleEndian};
+use bytes::{BufMut, BytesMut};
+use tokio::codec::{Decoder, Encoder};
+
+/// `tag | request_id | op | payload_len` header preceding every frame.
+pub const HEADER_LEN: usize = 1 + 4 + 1 + 4;
+
+pub const TAG_REQUEST: u8 = 0;
+pub const TAG_RESPONSE: u8 = 1;
+
+/// Operation discriminator shared by request and response frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    RegistersRead = 0,
+    PhysRead = 1,
+    PhysWrite = 2,
+    VirtRead = 3,
+    VirtWrite = 4,
+    /// Scatter/gather physical read: payload is `count:u32` followed by
+    /// `count` `(address:u64, length:u64)` descriptors; the response payload
+    /// is the descriptors' data concatenated in the same order.
+    PhysReadVectored = 5,
+    /// Scatter/gather physical write: payload is `count:u32` followed by
+    /// `count` `(address:u64, length:u64)` descriptors and then the
+    /// descriptors' data concatenated in the same order; the response
+    /// payload is `count` `length:u64` values written.
+    PhysWriteVectored = 6,
+}
+
+impl Op {
+    fn from_u8(v: u8) -> io::Result<Self> {
+        match v {
+            0 => Ok(Op::RegistersRead),
+            1 => Ok(Op::PhysRead),
+            2 => Ok(Op::PhysWrite),
+            3 => Ok(Op::VirtRead),
+            4 => Ok(Op::VirtWrite),
+            5 => Ok(Op::PhysReadVectored),
+            6 => Ok(Op::PhysWriteVectored),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "invalid bridge op")),
+        }
+    }
+}
+
+/// A single length-delimited frame on the bridge wire.
+///
+/// Layout: `tag:u8 | request_id:u32 | op:u8 | payload_len:u32 | payload`.
+/// `request_id` is chosen by the client and echoed back on the matching
+/// response frame so replies can be demultiplexed even when they arrive out
+/// of order.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub tag: u8,
+    pub request_id: u32,
+    pub op: Op,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    pub fn request(request_id: u32, op: Op, payload: Vec<u8>) -> Self {
+        Self {
+            tag: TAG_REQUEST,
+            request_id,
+            op,
+            payload,
+        }
+    }
+
+    pub fn response(request_id: u32, op: Op, payload: Vec<u8>) -> Self {
+        Self {
+            tag: TAG_RESPONSE,
+            request_id,
+            op,
+            payload,
+        }
+    }
+}
+
+/// Frames the raw bridge byte stream into [`Frame`]s.
+///
+/// Handles partial reads and back-pressure: [`decode`](Decoder::decode)
+/// returns `Ok(None)` until a full header and payload have arrived, so many
+/// `phys_read`/`virt_read` requests can be pipelined onto the same
+/// connection and their replies matched up as they trickle in.
+pub struct BridgeCodec;
+
+impl Decoder for BridgeCodec {
+    type Item = Frame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<Frame>> {
+        if src.len() < HEADER_LEN {
+            return Ok(None);
+        }
+
+        let tag = src[0];
+        let request_id = LittleEndian::read_u32(&src[1..5]);
+        let op = Op::from_u8(src[5])?;
+        let payload_len = LittleEndian::read_u32(&src[6..10]) as usize;
+
+        if src.len() < HEADER_LEN + payload_len {
+            // not enough bytes for the full payload yet; reserve the room
+            // and wait for the next read to bring more in
+            src.reserve(HEADER_LEN + payload_len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(HEADER_LEN);
+        let payload = src.split_to(payload_len).to_vec();
+
+        Ok(Some(Frame {
+            tag,
+            request_id,
+            op,
+            payload,
+        }))
+    }
+}
+
+impl Encoder for BridgeCodec {
+    type Item = Frame;
+    type Error = io::Error;
+
+    fn encode(&mut self, frame: Frame, dst: &mut BytesMut) -> io::Result<()> {
+        dst.reserve(HEADER_LEN + frame.payload.len());
+        dst.put_u8(frame.tag);
+
+        let mut request_id_buf = [0u8; 4];
+        LittleEndian::write_u32(&mut request_id_buf, frame.request_id);
+        dst.put_slice(&request_id_buf);
+
+        dst.put_u8(frame.op as u8);
+
+        let mut len_buf = [0u8; 4];
+        LittleEndian::write_u32(&mut len_buf, frame.payload.len() as u32);
+        dst.put_slice(&len_buf);
+
+        dst.put_slice(&frame.payload);
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+mod client;
+mod codec;
+
+pub use client::BridgeClient;
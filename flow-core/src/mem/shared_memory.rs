@@ -0,0 +1,75 @@
+use std::fs::File;
+use std::io::{Error, ErrorKind, Result};
+use std::os::unix::io::{FromRawFd, RawFd};
+
+use memmap::Mmap;
+
+use crate::address::{Address, Length};
+use crate::mem::{PhysicalRead, PhysicalWrite};
+
+/// A zero-copy physical memory backend over an `mmap`-ed shared region.
+///
+/// For same-host introspection (e.g. a hypervisor exposing guest RAM as a
+/// file) this lets `phys_read` slice directly out of the mapping instead of
+/// copying bytes through a request/response round-trip.
+pub struct SharedMemoryConnector {
+    map: Mmap,
+}
+
+impl SharedMemoryConnector {
+    /// Wraps an already-open file descriptor pointing at the shared region,
+    /// typically one handed over out-of-band via `SCM_RIGHTS` by
+    /// [`BridgeClient::connect_unix`](crate::bridge::BridgeClient).
+    ///
+    /// # Safety
+    ///
+    /// `fd` must be a valid, open file descriptor referring to a mappable
+    /// region; ownership of the descriptor is transferred to the returned
+    /// connector.
+    pub unsafe fn from_raw_fd(fd: RawFd) -> Result<Self> {
+        let file = File::from_raw_fd(fd);
+        let map = Mmap::map(&file)?;
+        Ok(Self { map })
+    }
+
+    /// Number of bytes covered by the shared mapping.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns whether `[addr, addr + len)` falls entirely within the
+    /// mapped region, i.e. whether a caller can use this connector's fast
+    /// path for it instead of falling back to RPC.
+    pub fn contains(&self, addr: Address, len: Length) -> bool {
+        self.slice(addr, len).is_some()
+    }
+
+    fn slice(&self, addr: Address, len: Length) -> Option<&[u8]> {
+        let start = addr.as_usize();
+        let end = start.checked_add(len.as_usize())?;
+        self.map.get(start..end)
+    }
+}
+
+impl PhysicalRead for SharedMemoryConnector {
+    fn phys_read(&mut self, addr: Address, len: Length) -> Result<Vec<u8>> {
+        self.slice(addr, len)
+            .map(|s| s.to_vec())
+            .ok_or_else(|| Error::new(ErrorKind::Other, "address out of shared memory range"))
+    }
+}
+
+impl PhysicalWrite for SharedMemoryConnector {
+    fn phys_write(&mut self, _addr: Address, _data: &Vec<u8>) -> Result<Length> {
+        // the mapping is opened read-only on the client side; writes always
+        // go through the bridge's RPC path instead
+        Err(Error::new(
+            ErrorKind::Other,
+            "shared memory connector is read-only",
+        ))
+    }
+}
@@ -13,26 +13,45 @@ use goblin::pe::PE;
 
 use crate::kernel::StartBlock;
 
+/// Result of [`find`]: the kernel's base address plus a usable DTB.
+///
+/// From a cold [`KernelScanner`] sweep, `base` is the kernel's physical base
+/// address and `dtb` is recovered from the low-stub self-map, since a cold
+/// scan has no virtual view (and thus no already-known DTB) to start from.
+/// From the va-hint path, `base` is the kernel's virtual base address and
+/// `dtb` is the one handed in via the `StartBlock`.
+pub struct KernelScanResult {
+    pub base: Address,
+    pub dtb: Address,
+}
+
 // TODO: -> Result<WinProcess>
 pub fn find<T: PhysicalRead + VirtualRead>(
     mem: &mut T,
     start_block: &StartBlock,
-) -> Result<Address> {
+) -> Result<KernelScanResult> {
     if start_block.arch.instruction_set == InstructionSet::X64 {
         if !start_block.va.is_null() {
             match find_x64_with_va(mem, start_block) {
-                Ok(b) => return Ok(b),
+                // the va-hint path already has a known-good dtb handed in
+                // via `start_block`, unlike the cold scan below
+                Ok(base) => {
+                    return Ok(KernelScanResult {
+                        base,
+                        dtb: start_block.dtb,
+                    })
+                }
                 Err(e) => warn!("{}", e),
             }
         }
 
         match find_x64(mem) {
-            Ok(b) => return Ok(b),
+            Ok(r) => return Ok(r),
             Err(e) => warn!("{}", e),
         }
     } else {
         match find_x86(mem) {
-            Ok(b) => return Ok(b),
+            Ok(r) => return Ok(r),
             Err(e) => println!("Error: {}", e),
         }
     }
@@ -117,10 +136,223 @@ fn find_x64_with_va<T: PhysicalRead + VirtualRead>(
     ))
 }
 
-fn find_x64<T: PhysicalRead + VirtualRead>(mem: &mut T) -> Result<Address> {
-    Err(Error::new("find_x64(): not implemented yet"))
+const MZ_MAGIC: u16 = 0x5a4d;
+const POOLCODE_TAG: u64 = 0x45444F434C4F4F50;
+
+// `jmp $+0x600e9`-style self-map sled used by the KPROCESS low stub to
+// identify the initial page tables in the first megabyte of physical memory.
+const LOW_STUB_SIGNATURE: u64 = 0x0000_0001_0006_00E9;
+
+/// Which PE variant a [`KernelScanner`] should accept: x86 kernels are
+/// PE32, x64 kernels are PE32+. Rejecting the other magic here is what
+/// keeps a 32-bit scan from matching a 64-bit `ntoskrnl.exe` candidate (or
+/// vice versa) purely on name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PeMagic {
+    Pe32,
+    Pe32Plus,
+}
+
+impl PeMagic {
+    fn optional_header_magic(self) -> u16 {
+        match self {
+            PeMagic::Pe32 => 0x10b,
+            PeMagic::Pe32Plus => 0x20b,
+        }
+    }
+}
+
+/// Arch-generic cold kernel scanner.
+///
+/// `find_x64_with_va` can probe around a known virtual address hint, but a
+/// cold scan has neither a hint nor a DTB to build a virtual view with, so
+/// this drives everything over `PhysicalRead` directly. x86, x86-PAE and x64
+/// only differ here in their page size and PE magic; the MZ -> POOLCODE ->
+/// PE staged filter is shared.
+struct KernelScanner<'a, T> {
+    mem: &'a mut T,
+    page_size: Length,
+    pe_magic: PeMagic,
 }
 
-fn find_x86<T: PhysicalRead + VirtualRead>(mem: &mut T) -> Result<Address> {
-    Err(Error::new("find_x86(): not implemented yet"))
+impl<'a, T: PhysicalRead> KernelScanner<'a, T> {
+    fn new(mem: &'a mut T, page_size: Length, pe_magic: PeMagic) -> Self {
+        Self {
+            mem,
+            page_size,
+            pe_magic,
+        }
+    }
+
+    /// Sweeps physical memory in 2 MB windows aligned to `0x1000`, testing
+    /// each page for the MZ header and then scanning 8-byte-aligned offsets
+    /// for the POOLCODE pool tag, same as `find_x64_with_va` does around its
+    /// va hint. Every candidate page is then probed with `goblin` and only
+    /// accepted once its export/name resolves to `ntoskrnl.exe`.
+    fn scan(&mut self) -> Result<Address> {
+        let window = Length::from_mb(2);
+        let page_size = self.page_size.as_usize();
+
+        let mut base = Address::from(0u64);
+        // A single unreadable window is just a memory hole (common below
+        // ntoskrnl), not the end of physical memory, so only a long run of
+        // them ends the sweep.
+        let mut consecutive_misses = 0usize;
+        const MAX_CONSECUTIVE_MISSES: usize = 16;
+
+        loop {
+            trace!("KernelScanner::scan: probing physical window at {:x}", base.as_u64());
+
+            let buf = match self.mem.phys_read(base, window) {
+                Ok(b) => b,
+                Err(_) => {
+                    consecutive_misses += 1;
+                    if consecutive_misses > MAX_CONSECUTIVE_MISSES {
+                        break;
+                    }
+                    base += window;
+                    continue;
+                }
+            };
+            consecutive_misses = 0;
+            if buf.is_empty() {
+                break;
+            }
+
+            let mut candidate = None;
+            for (i, page) in buf.chunks_exact(page_size).enumerate() {
+                if LittleEndian::read_u16(&page) != MZ_MAGIC {
+                    continue;
+                }
+                trace!("KernelScanner::scan: found potential MZ flag at offset {:x}", i * page_size);
+
+                let has_poolcode = page
+                    .chunks_exact(8)
+                    .any(|c| LittleEndian::read_u64(&c) == POOLCODE_TAG);
+                if !has_poolcode {
+                    continue;
+                }
+                trace!("KernelScanner::scan: found potential POOLCODE flag at offset {:x}", i * page_size);
+
+                let probe_addr = base + (i * page_size) as u64;
+                if self.probe_pe(probe_addr) {
+                    candidate = Some(probe_addr);
+                    break;
+                }
+            }
+
+            if let Some(addr) = candidate {
+                return Ok(addr);
+            }
+
+            base += window;
+        }
+
+        Err(Error::new("KernelScanner::scan: unable to locate ntoskrnl.exe"))
+    }
+
+    /// Parses the PE at `addr` (with `resolve_rva = false`, same as
+    /// `find_x64_with_va`), and returns whether it's both the expected
+    /// `self.pe_magic` variant and resolves to `ntoskrnl.exe`.
+    fn probe_pe(&mut self, addr: Address) -> bool {
+        let probe_buf = match self.mem.phys_read(addr, Length::from_mb(32)) {
+            Ok(b) => b,
+            Err(_) => return false,
+        };
+
+        let mut pe_opts = ParseOptions::default();
+        pe_opts.resolve_rva = false;
+
+        let pe = match PE::parse_with_opts(&probe_buf, &pe_opts) {
+            Ok(pe) => pe,
+            Err(e) => {
+                trace!(
+                    "KernelScanner::probe_pe: candidate at {:x} could not be probed: {:?}",
+                    addr.as_u64(),
+                    e
+                );
+                return false;
+            }
+        };
+
+        let magic_matches = pe
+            .header
+            .optional_header
+            .map(|oh| oh.standard_fields.magic == self.pe_magic.optional_header_magic())
+            .unwrap_or(false);
+        if !magic_matches {
+            trace!(
+                "KernelScanner::probe_pe: candidate at {:x} has the wrong pe magic for {:?}",
+                addr.as_u64(),
+                self.pe_magic
+            );
+            return false;
+        }
+
+        info!("KernelScanner::probe_pe: found pe header for {}", pe.name.unwrap_or_default());
+        pe.name.unwrap_or_default() == "ntoskrnl.exe"
+    }
+
+    /// Recovers the kernel DTB by locating the `KPROCESS` low-stub self-map
+    /// signature in the first megabyte of physical memory. The self-ref
+    /// sits at the very start of the page-aligned stub (not at an arbitrary
+    /// 8-byte offset within it), and its second byte (bits 8..16) varies, so
+    /// the compare masks that byte out; the DTB/PML4 itself is the CR3 value stored at
+    /// offset `0xa0` into that same page (`0x70` is the kernel-base VA hint,
+    /// not the dtb), and is sanity-checked as a page-aligned, non-zero
+    /// physical address before being trusted.
+    fn find_dtb(&mut self) -> Result<Address> {
+        let buf = self.mem.phys_read(Address::from(0u64), Length::from_mb(1))?;
+        let page_size = self.page_size.as_usize();
+
+        buf.chunks_exact(page_size)
+            .skip(1) // page 0 is never the low stub
+            .find(|page| {
+                LittleEndian::read_u64(&page[0..8]) & 0xffff_ffff_ffff_00ff == LOW_STUB_SIGNATURE
+            })
+            .and_then(|page| {
+                let dtb = LittleEndian::read_u64(&page[0xa0..0xa8]);
+                if dtb != 0 && dtb & 0xfff == 0 {
+                    Some(Address::from(dtb))
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| Error::new("KernelScanner::find_dtb: unable to locate kernel dtb low stub"))
+    }
+}
+
+fn find_x64<T: PhysicalRead + VirtualRead>(mem: &mut T) -> Result<KernelScanResult> {
+    trace!("find_x64: cold-scanning physical memory for ntoskrnl.exe");
+
+    let mut scanner = KernelScanner::new(mem, arch::x64::page_size(), PeMagic::Pe32Plus);
+    let base = scanner.scan()?;
+    let dtb = scanner.find_dtb()?;
+
+    Ok(KernelScanResult { base, dtb })
+}
+
+fn find_x86<T: PhysicalRead + VirtualRead>(mem: &mut T) -> Result<KernelScanResult> {
+    trace!("find_x86: cold-scanning physical memory for ntoskrnl.exe");
+
+    // this tree's `arch` crate only ever exposes x64's page size elsewhere
+    // (`find_x64_with_va`), so rather than guess at an `arch::x86` module
+    // this hasn't been seen referenced: x86 non-PAE pages are 4 KB, same as
+    // x64's, so reuse the symbol that's actually known to exist. PE32 (not
+    // PE32+) is what distinguishes a genuine 32-bit match here.
+    let mut scanner = KernelScanner::new(mem, arch::x64::page_size(), PeMagic::Pe32);
+    let base = scanner.scan()?;
+
+    // `find_dtb`'s low-stub signature and its 0xa0 dtb offset are the x64
+    // KPROCESS layout; the 32-bit low stub differs and hasn't been
+    // reverse-engineered in this tree, so reusing them here would hand
+    // callers a wrong dtb instead of a usable one. Report the base we did
+    // find and fail rather than guess at one.
+    debug!(
+        "find_x86: located ntoskrnl.exe base at {:x} but cold dtb recovery is not implemented for x86 targets",
+        base.as_u64()
+    );
+    Err(Error::new(
+        "find_x86: cold dtb recovery is not implemented for x86 targets",
+    ))
 }
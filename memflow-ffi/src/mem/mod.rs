@@ -0,0 +1,5 @@
+mod phys_mem;
+mod phys_mem_bus;
+
+pub use phys_mem::*;
+pub use phys_mem_bus::*;
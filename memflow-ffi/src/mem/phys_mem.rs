@@ -23,6 +23,89 @@ pub unsafe extern "C" fn phys_read_raw_list(
     mem.phys_read_raw_list(data).as_int_result()
 }
 
+/// A single scatter/gather descriptor for [`phys_read_vectored`]: a
+/// `PhysicalAddress` to read from, paired with the destination buffer that
+/// should receive the bytes.
+///
+/// # Safety
+///
+/// `buf` must be a valid pointer to a buffer of at least `buf_len` bytes for
+/// the duration of the call.
+#[repr(C)]
+pub struct PhysicalReadVectored {
+    pub addr: PhysicalAddress,
+    pub buf: *mut u8,
+    pub buf_len: usize,
+}
+
+/// A single scatter/gather descriptor for [`phys_write_vectored`]: a
+/// `PhysicalAddress` to write to, paired with the source buffer the bytes
+/// should be written from.
+///
+/// # Safety
+///
+/// `buf` must be a valid pointer to a buffer of at least `buf_len` bytes for
+/// the duration of the call.
+#[repr(C)]
+pub struct PhysicalWriteVectored {
+    pub addr: PhysicalAddress,
+    pub buf: *const u8,
+    pub buf_len: usize,
+}
+
+/// Read a list of non-contiguous ranges directly into their destination
+/// buffers.
+///
+/// This builds a `PhysicalReadData` batch straight out of the caller's own
+/// buffers (no intermediate owned allocation per range) and issues it
+/// through the existing [`phys_read_raw_list`], rather than adding a second
+/// code path. It does not itself issue a vectored `readv`/`recvmsg` syscall
+/// here at the FFI layer; whether the descriptors end up as one such
+/// transfer is up to the underlying connector's own `phys_read_raw_list`
+/// implementation.
+///
+/// # Safety
+///
+/// `data` must be a valid array of `PhysicalReadVectored` with the length of
+/// at least `len`, and every descriptor's `buf` must be valid for `buf_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn phys_read_vectored(
+    mem: &mut ConnectorInstance,
+    data: *mut PhysicalReadVectored,
+    len: usize,
+) -> i32 {
+    let data = from_raw_parts_mut(data, len);
+    let mut batch: Vec<PhysicalReadData> = data
+        .iter_mut()
+        .map(|d| PhysicalReadData(d.addr, from_raw_parts_mut(d.buf, d.buf_len)))
+        .collect();
+    mem.phys_read_raw_list(&mut batch).as_int_result()
+}
+
+/// Write a list of non-contiguous ranges directly from their source
+/// buffers, mirroring [`phys_read_vectored`] by delegating to the existing
+/// [`phys_write_raw_list`].
+///
+/// # Safety
+///
+/// `data` must be a valid array of `PhysicalWriteVectored` with the length
+/// of at least `len`, and every descriptor's `buf` must be valid for
+/// `buf_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn phys_write_vectored(
+    mem: &mut ConnectorInstance,
+    data: *const PhysicalWriteVectored,
+    len: usize,
+) -> i32 {
+    let data = from_raw_parts(data, len);
+    let batch: Vec<PhysicalWriteData> = data
+        .iter()
+        .map(|d| PhysicalWriteData(d.addr, from_raw_parts(d.buf, d.buf_len)))
+        .collect();
+    mem.phys_write_raw_list(&batch).as_int_result()
+}
+
 /// Write a list of values
 ///
 /// This will perform `len` physical memory writes on the provided `data`. Using lists is preferable
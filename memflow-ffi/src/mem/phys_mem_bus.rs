@@ -0,0 +1,235 @@
+use memflow::error::{Error, Result};
+use memflow::mem::phys_mem::*;
+use memflow::plugins::ConnectorInstance;
+use memflow::types::Address;
+
+/// A physical address range owned by one backend registered with a
+/// [`PhysicalMemoryBus`], modeled on a hardware memory bridge mapping
+/// address windows to ports.
+#[derive(Debug, Clone, Copy)]
+pub struct AddressRange {
+    pub base: Address,
+    pub size: usize,
+    pub backend_id: usize,
+}
+
+impl AddressRange {
+    /// Whether the half-open span `[addr, addr + len)` lies entirely within
+    /// this range. A descriptor that only starts inside the range but runs
+    /// past its end does not belong to it: routing it here wholesale would
+    /// read or write past this backend's registered window into whatever
+    /// comes after it.
+    fn contains_range(&self, addr: Address, len: usize) -> bool {
+        addr >= self.base && addr + len <= self.base + self.size
+    }
+}
+
+/// What a [`PhysicalMemoryBus`] does with an address that falls outside
+/// every registered backend's range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnmappedPolicy {
+    /// Fail the request with an error.
+    Error,
+    /// Treat the range as zeroed memory: reads return zeroes, writes are
+    /// silently discarded.
+    ZeroFill,
+}
+
+/// Routes physical reads/writes across several registered backend
+/// connectors by target address, so callers can overlay MMIO regions, model
+/// memory holes, or combine a fast cached RAM snapshot with a live connector
+/// for device memory.
+///
+/// Overlapping ranges are resolved by registration order: the first backend
+/// whose range contains the whole `[address, address + len)` span wins.
+pub struct PhysicalMemoryBus {
+    backends: Vec<(AddressRange, ConnectorInstance)>,
+    unmapped: UnmappedPolicy,
+}
+
+impl PhysicalMemoryBus {
+    pub fn new(unmapped: UnmappedPolicy) -> Self {
+        Self {
+            backends: Vec::new(),
+            unmapped,
+        }
+    }
+
+    pub fn register(&mut self, range: AddressRange, backend: ConnectorInstance) -> &mut Self {
+        self.backends.push((range, backend));
+        self
+    }
+
+    fn backend_index_for_range(&self, addr: Address, len: usize) -> Option<usize> {
+        self.backends
+            .iter()
+            .position(|(range, _)| range.contains_range(addr, len))
+    }
+
+    /// Partitions `data` by the backend whose range fully contains each
+    /// entry's `[address, address + len)` span, dispatches each partition to
+    /// that backend's own list call (preserving batching), and stitches
+    /// results back into `data` in original order.
+    ///
+    /// An entry that straddles two backends' ranges doesn't fully belong to
+    /// either one, so it's treated as unmapped (per `self.unmapped`) rather
+    /// than routed wholesale to whichever backend owns its start address,
+    /// which would over-read/write into the neighboring range.
+    fn route_reads(&mut self, data: &mut [PhysicalReadData]) -> Result<()> {
+        let mut by_backend: Vec<Vec<usize>> = vec![Vec::new(); self.backends.len()];
+        let mut unmapped_indices = Vec::new();
+
+        for (i, read) in data.iter().enumerate() {
+            match self.backend_index_for_range(read.0.address(), read.1.len()) {
+                Some(idx) => by_backend[idx].push(i),
+                None => unmapped_indices.push(i),
+            }
+        }
+
+        for (idx, indices) in by_backend.into_iter().enumerate() {
+            if indices.is_empty() {
+                continue;
+            }
+
+            // `PhysicalReadData`'s destination buffer is a `&mut [u8]`, so it
+            // can't be cloned without aliasing the caller's real buffer.
+            // Move each entry's buffer out of `data` instead, leaving an
+            // empty placeholder behind until the backend's result is
+            // written back below.
+            let mut partition: Vec<PhysicalReadData> = indices
+                .iter()
+                .map(|&i| {
+                    let addr = data[i].0;
+                    let buf = std::mem::replace(&mut data[i].1, &mut []);
+                    PhysicalReadData(addr, buf)
+                })
+                .collect();
+            self.backends[idx].1.phys_read_raw_list(&mut partition)?;
+            for (&i, read) in indices.iter().zip(partition.into_iter()) {
+                data[i].1 = read.1;
+            }
+        }
+
+        match self.unmapped {
+            UnmappedPolicy::ZeroFill => {
+                for i in unmapped_indices {
+                    for b in data[i].1.iter_mut() {
+                        *b = 0;
+                    }
+                }
+            }
+            UnmappedPolicy::Error => {
+                if !unmapped_indices.is_empty() {
+                    return Err(Error::Connector(
+                        "address not mapped by any backend on PhysicalMemoryBus",
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Same straddling-range handling as `route_reads`: a write whose span
+    /// crosses out of its start backend's range is treated as unmapped
+    /// rather than partially applied to the wrong backend.
+    fn route_writes(&mut self, data: &[PhysicalWriteData]) -> Result<()> {
+        let mut by_backend: Vec<Vec<usize>> = vec![Vec::new(); self.backends.len()];
+        let mut unmapped = false;
+
+        for (i, write) in data.iter().enumerate() {
+            match self.backend_index_for_range(write.0.address(), write.1.len()) {
+                Some(idx) => by_backend[idx].push(i),
+                None => unmapped = true,
+            }
+        }
+
+        for (idx, indices) in by_backend.into_iter().enumerate() {
+            if indices.is_empty() {
+                continue;
+            }
+
+            let partition: Vec<PhysicalWriteData> =
+                indices.iter().map(|&i| data[i].clone()).collect();
+            self.backends[idx].1.phys_write_raw_list(&partition)?;
+        }
+
+        if unmapped && self.unmapped == UnmappedPolicy::Error {
+            return Err(Error::Connector(
+                "address not mapped by any backend on PhysicalMemoryBus",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a new, empty `PhysicalMemoryBus` with the given unmapped-address
+/// policy, returning an owning pointer the caller must eventually pass to
+/// [`phys_mem_bus_free`].
+#[no_mangle]
+pub extern "C" fn phys_mem_bus_new(unmapped: UnmappedPolicy) -> *mut PhysicalMemoryBus {
+    Box::into_raw(Box::new(PhysicalMemoryBus::new(unmapped)))
+}
+
+/// Registers `backend` with `bus` to serve `range`, taking ownership of the
+/// connector.
+///
+/// # Safety
+///
+/// `bus` must be a valid pointer previously returned by
+/// [`phys_mem_bus_new`].
+#[no_mangle]
+pub unsafe extern "C" fn phys_mem_bus_register(
+    bus: &mut PhysicalMemoryBus,
+    range: AddressRange,
+    backend: ConnectorInstance,
+) {
+    bus.register(range, backend);
+}
+
+/// Frees a `PhysicalMemoryBus` previously created with
+/// [`phys_mem_bus_new`], dropping every backend registered with it.
+///
+/// # Safety
+///
+/// `bus` must be a valid pointer previously returned by
+/// [`phys_mem_bus_new`], and must not be used again afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn phys_mem_bus_free(bus: *mut PhysicalMemoryBus) {
+    let _ = Box::from_raw(bus);
+}
+
+impl PhysicalMemory for PhysicalMemoryBus {
+    fn phys_read_raw_list(&mut self, data: &mut [PhysicalReadData]) -> Result<()> {
+        self.route_reads(data)
+    }
+
+    fn phys_write_raw_list(&mut self, data: &[PhysicalWriteData]) -> Result<()> {
+        self.route_writes(data)
+    }
+
+    /// Reports the union of every backend's size/page-size, so consumers
+    /// like `virt_page_map` keep seeing a single coherent layout.
+    fn metadata(&self) -> PhysicalMemoryMetadata {
+        let max_address = self
+            .backends
+            .iter()
+            .map(|(range, _)| range.base + range.size)
+            .max()
+            .unwrap_or_else(|| Address::from(0));
+
+        let max_page_size = self
+            .backends
+            .iter()
+            .map(|(_, backend)| backend.metadata().max_page_size)
+            .max()
+            .unwrap_or(0x1000);
+
+        PhysicalMemoryMetadata {
+            size: max_address.as_usize(),
+            readonly: self.backends.iter().all(|(_, backend)| backend.metadata().readonly),
+            max_page_size,
+        }
+    }
+}